@@ -46,6 +46,134 @@ fn rust_simulation(_py: Python, m: &PyModule) -> PyResult<()> {
         (day_of_year_out, local_time_out)
     }
 
+    /// Decompose a unix timestamp into its day-of-year `n` and local decimal hour,
+    /// matching the convention used by `rust_calculate_array_ghi_times`.
+    fn day_of_year_and_decimal_hour(unix_time_stamp: i64) -> (f64, f64) {
+        let datetime = NaiveDateTime::from_timestamp_opt(unix_time_stamp, 0).unwrap();
+        let n = datetime.date().ordinal() as f64;
+        let decimal_hour = datetime.time().num_seconds_from_midnight() as f64 / 3600.0;
+        (n, decimal_hour)
+    }
+
+    /// Self-contained solar geometry for a PV surface. For each tick this computes the
+    /// solar zenith, azimuth, declination and hour angle, plus the angle of incidence on a
+    /// panel with the given `panel_tilt` and `panel_azimuth`. All trigonometry is carried
+    /// out in radians internally; the returned vectors are in degrees to match the f64 array
+    /// conventions of the rest of the module. Zenith angles beyond `zenith_limit` (the sun
+    /// below the working horizon) are clamped to that limit and the incidence angle is set to
+    /// 90 degrees so that downstream irradiance evaluates to zero.
+    fn rust_solar_position(
+        unix_timestamps: ArrayViewD<'_, i64>,
+        latitudes: ArrayViewD<'_, f64>,
+        longitudes: ArrayViewD<'_, f64>,
+        panel_tilt: f64,
+        panel_azimuth: f64,
+        zenith_limit: f64,
+    ) -> (Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>) {
+        let ticks = unix_timestamps.len();
+        let mut zenith_out: Vec<f64> = Vec::with_capacity(ticks);
+        let mut azimuth_out: Vec<f64> = Vec::with_capacity(ticks);
+        let mut declination_out: Vec<f64> = Vec::with_capacity(ticks);
+        let mut hour_angle_out: Vec<f64> = Vec::with_capacity(ticks);
+        let mut incidence_out: Vec<f64> = Vec::with_capacity(ticks);
+
+        let tilt = panel_tilt.to_radians();
+
+        for (i, &unix_timestamp) in unix_timestamps.iter().enumerate() {
+            let (n, decimal_hour) = day_of_year_and_decimal_hour(unix_timestamp);
+            let latitude = latitudes[i];
+            let longitude = longitudes[i];
+
+            let declination_deg = 23.45 * (360.0 * (284.0 + n) / 365.0).to_radians().sin();
+
+            let b = (360.0 * (n - 1.0) / 365.0).to_radians();
+            let equation_of_time = 229.18
+                * (0.000075 + 0.001868 * b.cos()
+                    - 0.032077 * b.sin()
+                    - 0.014615 * (2.0 * b).cos()
+                    - 0.04089 * (2.0 * b).sin());
+
+            // Solar time in hours: local time plus the equation of time and the longitude
+            // offset (four minutes of time per degree of longitude).
+            let solar_time = decimal_hour + equation_of_time / 60.0 + longitude / 15.0;
+            let hour_angle_deg = 15.0 * (solar_time - 12.0);
+
+            let phi = latitude.to_radians();
+            let delta = declination_deg.to_radians();
+            let h = hour_angle_deg.to_radians();
+
+            let cos_zenith = phi.sin() * delta.sin() + phi.cos() * delta.cos() * h.cos();
+            let mut zenith = cos_zenith.clamp(-1.0, 1.0).acos().to_degrees();
+
+            // Azimuth measured clockwise from north, with solar noon at 180 degrees.
+            let azimuth_south = h.sin().atan2(h.cos() * phi.sin() - delta.tan() * phi.cos());
+            let azimuth = azimuth_south.to_degrees() + 180.0;
+
+            let below_horizon = zenith > zenith_limit;
+            if below_horizon {
+                zenith = zenith_limit;
+            }
+
+            let incidence = if below_horizon {
+                90.0
+            } else {
+                let altitude = (90.0 - zenith).to_radians();
+                let surface_solar_azimuth = ((azimuth - 180.0) - panel_azimuth).abs().to_radians();
+                let cos_incidence = altitude.cos() * surface_solar_azimuth.cos() * tilt.sin()
+                    + altitude.sin() * tilt.cos();
+                cos_incidence.clamp(-1.0, 1.0).acos().to_degrees()
+            };
+
+            zenith_out.push(zenith);
+            azimuth_out.push(azimuth);
+            declination_out.push(declination_deg);
+            hour_angle_out.push(hour_angle_deg);
+            incidence_out.push(incidence);
+        }
+
+        (
+            zenith_out,
+            azimuth_out,
+            declination_out,
+            hour_angle_out,
+            incidence_out,
+        )
+    }
+
+    /// Project global horizontal irradiance onto the car's tilted PV surface using the solar
+    /// geometry from `rust_solar_position`. The plane-of-array value is `ghi * cos(theta)`,
+    /// with negative projections and ticks whose zenith reaches `zenith_limit` clamped to zero.
+    fn rust_incident_irradiance(
+        unix_timestamps: ArrayViewD<'_, i64>,
+        latitudes: ArrayViewD<'_, f64>,
+        longitudes: ArrayViewD<'_, f64>,
+        ghi: ArrayViewD<'_, f64>,
+        panel_tilt: f64,
+        panel_azimuth: f64,
+        zenith_limit: f64,
+    ) -> Vec<f64> {
+        let (zenith, _azimuth, _declination, _hour_angle, incidence) = rust_solar_position(
+            unix_timestamps,
+            latitudes,
+            longitudes,
+            panel_tilt,
+            panel_azimuth,
+            zenith_limit,
+        );
+
+        incidence
+            .iter()
+            .enumerate()
+            .map(|(i, &theta)| {
+                if zenith[i] >= zenith_limit {
+                    0.0
+                } else {
+                    (ghi[i] * theta.to_radians().cos()).max(0.0)
+                }
+            })
+            .collect()
+    }
+
     fn rust_closest_gis_indices_loop(
         cumulative_distances: ArrayViewD<'_, f64>,
         average_distances: ArrayViewD<'_, f64>,
@@ -93,11 +221,190 @@ fn rust_simulation(_py: Python, m: &PyModule) -> PyResult<()> {
         result
     }
 
+    /// Companion to `rust_closest_gis_indices_loop` that, instead of snapping each tick to one
+    /// coordinate, returns the lower bracketing index `i` and a weight `alpha in [0, 1]` giving the
+    /// tick's fractional position between `average_distances[i]` and `average_distances[i + 1]`.
+    /// The final coordinate clamps `alpha = 0`, as do zero-length segments
+    /// (`average_distances[i + 1] == average_distances[i]`).
+    fn rust_fractional_indices_loop(
+        cumulative_distances: ArrayViewD<'_, f64>,
+        average_distances: ArrayViewD<'_, f64>,
+    ) -> (Vec<i64>, Vec<f64>) {
+        let coords = average_distances.len();
+        let mut lower_indices: Vec<i64> = Vec::with_capacity(cumulative_distances.len());
+        let mut alphas: Vec<f64> = Vec::with_capacity(cumulative_distances.len());
+        let mut current_coord_index: usize = 0;
+
+        for &distance in cumulative_distances {
+            // Forward-only monotone pointer: the route progresses along the coordinates.
+            while current_coord_index + 1 < coords
+                && distance > average_distances[current_coord_index + 1]
+            {
+                current_coord_index += 1;
+            }
+
+            let alpha = if current_coord_index + 1 >= coords {
+                0.0
+            } else {
+                let d_lower = average_distances[current_coord_index];
+                let d_upper = average_distances[current_coord_index + 1];
+                if d_upper == d_lower {
+                    0.0
+                } else {
+                    ((distance - d_lower) / (d_upper - d_lower)).clamp(0.0, 1.0)
+                }
+            };
+
+            lower_indices.push(current_coord_index as i64);
+            alphas.push(alpha);
+        }
+
+        (lower_indices, alphas)
+    }
+
+    /// Apply the fractional weights from `rust_fractional_indices_loop` to linearly interpolate
+    /// each tick's endpoint vector between the forecasts at consecutive coordinates
+    /// (`weather[i] + alpha * (weather[i + 1] - weather[i])`). `weather` is a 2-D
+    /// `[coordinates, endpoints]` array; the result is `[ticks, endpoints]`.
+    fn rust_blend_weather(
+        weather: ArrayViewD<'_, f64>,
+        lower_indices: &[i64],
+        alphas: &[f64],
+    ) -> Array2<f64> {
+        let raw_dim = weather.raw_dim();
+        let coords = raw_dim[0];
+        let endpoints = raw_dim[1];
+        let output_shape = (lower_indices.len(), endpoints);
+
+        let mut placeholder: Vec<f64> = vec![0.0; output_shape.0 * output_shape.1];
+        let mut blended = ArrayViewMut2::from_shape(output_shape, &mut placeholder).unwrap();
+
+        for (tick, (&lower, &alpha)) in lower_indices.iter().zip(alphas.iter()).enumerate() {
+            let lower = lower as usize;
+            let upper = std::cmp::min(lower + 1, coords - 1);
+            let lower_row = weather.slice(s![lower, ..]);
+            let upper_row = weather.slice(s![upper, ..]);
+            for endpoint in 0..endpoints {
+                let w0 = lower_row[endpoint];
+                let w1 = upper_row[endpoint];
+                blended[[tick, endpoint]] = w0 + (w1 - w0) * alpha;
+            }
+        }
+
+        blended.into_owned()
+    }
+
+    /// Great-circle distance in metres between two `(lat, lon)` points given in degrees.
+    fn haversine_distance(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+        const EARTH_RADIUS_M: f64 = 6_371_000.0;
+        let phi1 = lat1.to_radians();
+        let phi2 = lat2.to_radians();
+        let delta_phi = (lat2 - lat1).to_radians();
+        let delta_lambda = (lon2 - lon1).to_radians();
+        let a = (delta_phi / 2.0).sin().powi(2)
+            + phi1.cos() * phi2.cos() * (delta_lambda / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_M * a.sqrt().asin()
+    }
+
+    /// Nearest grid coordinate per tick by great-circle distance, taking the route and grid
+    /// `(lat, lon)` arrays directly rather than precomputed cumulative distances. By default the
+    /// search keeps the forward-only monotone pointer of the cumulative-distance loops (the route
+    /// progresses along the grid, so each search starts at the previous index and only advances).
+    /// Set `strict` for a full nearest search per tick, needed for non-monotone or branching
+    /// routes.
+    fn rust_closest_indices_haversine(
+        route_latitudes: ArrayViewD<'_, f64>,
+        route_longitudes: ArrayViewD<'_, f64>,
+        grid_latitudes: ArrayViewD<'_, f64>,
+        grid_longitudes: ArrayViewD<'_, f64>,
+        strict: bool,
+    ) -> Vec<i64> {
+        let grid_len = grid_latitudes.len();
+        let mut result: Vec<i64> = Vec::with_capacity(route_latitudes.len());
+        let mut current_coord_index: usize = 0;
+
+        for (tick, &route_lat) in route_latitudes.iter().enumerate() {
+            let route_lon = route_longitudes[tick];
+
+            if strict {
+                let mut best_index = 0;
+                let mut best_distance = f64::INFINITY;
+                for grid_index in 0..grid_len {
+                    let distance = haversine_distance(
+                        route_lat,
+                        route_lon,
+                        grid_latitudes[grid_index],
+                        grid_longitudes[grid_index],
+                    );
+                    if distance < best_distance {
+                        best_distance = distance;
+                        best_index = grid_index;
+                    }
+                }
+                result.push(best_index as i64);
+            } else {
+                let mut best_index = current_coord_index;
+                let mut best_distance = haversine_distance(
+                    route_lat,
+                    route_lon,
+                    grid_latitudes[best_index],
+                    grid_longitudes[best_index],
+                );
+                while best_index + 1 < grid_len {
+                    let distance = haversine_distance(
+                        route_lat,
+                        route_lon,
+                        grid_latitudes[best_index + 1],
+                        grid_longitudes[best_index + 1],
+                    );
+                    if distance <= best_distance {
+                        best_distance = distance;
+                        best_index += 1;
+                    } else {
+                        break;
+                    }
+                }
+                current_coord_index = best_index;
+                result.push(best_index as i64);
+            }
+        }
+
+        result
+    }
+
+    // Mode `0` keeps the historical nearest-timestamp snapping; mode `1` linearly blends
+    // between the two bracketing forecast timestamps.
+    const WEATHER_MODE_LINEAR: u8 = 1;
+
+    /// Locate the forecast rows bracketing `t` within a coordinate's monotonically increasing
+    /// `dt_local_array`. Returns the lower and upper row indices together with the blend weight
+    /// `w = (t - t0) / (t1 - t0)`. Timestamps outside the forecast range clamp to the nearest
+    /// endpoint (no extrapolation) and coincident endpoints collapse to the lower row.
+    fn timestamp_bracket(dt_local_array: &[i64], t: i64) -> (usize, usize, f64) {
+        let times = dt_local_array.len();
+        let upper = dt_local_array.partition_point(|&forecast_time| forecast_time <= t);
+        if upper == 0 {
+            return (0, 0, 0.0);
+        }
+        if upper >= times {
+            return (times - 1, times - 1, 0.0);
+        }
+        let lower = upper - 1;
+        let t0 = dt_local_array[lower];
+        let t1 = dt_local_array[upper];
+        if t0 == t1 {
+            return (lower, lower, 0.0);
+        }
+        let weight = (t - t0) as f64 / (t1 - t0) as f64;
+        (lower, upper, weight)
+    }
+
     fn rust_weather_in_time(
         unix_timestamps: ArrayViewD<'_, i64>,
         indices: ArrayViewD<'_, i64>,
         weather_forecast: ArrayViewD<f64>,
-        dt_index: u8
+        dt_index: u8,
+        mode: u8,
     ) -> Array2<f64> {
         // Obtain dimensions for arrays and slices
         let weather_forecast_raw_dim = weather_forecast.raw_dim();
@@ -137,22 +444,39 @@ fn rust_simulation(_py: Python, m: &PyModule) -> PyResult<()> {
             dt_local_array.push(timestamp as i64);
         }
 
-        let closest_timestamp_indices =
-            rust_closest_timestamp_indices(unix_timestamps, dt_local_array);
-
         // Create a mutable array of the desired shape with dummy initial values
         let mut placeholder2: Vec<f64> =
             vec![0.0; weather_in_time_shape.0 * weather_in_time_shape.1];
         let mut weather_in_time_arrayview =
             ArrayViewMut2::from_shape(weather_in_time_shape, &mut placeholder2).unwrap();
-        for (index_1, &index_2) in closest_timestamp_indices.iter().enumerate() {
-            let slice_1d = indexed_forecast
-                .slice(s![index_1, index_2, ..])
-                .into_shape(full_forecast_shape.2)
-                .unwrap();
-            weather_in_time_arrayview
-                .slice_mut(s![index_1, ..])
-                .assign(&slice_1d);
+
+        if mode == WEATHER_MODE_LINEAR {
+            // Linearly blend the two forecast rows bracketing each tick timestamp across the
+            // whole endpoint vector, giving smooth transitions between forecast hours.
+            for index_1 in 0..weather_in_time_shape.0 {
+                let (lower, upper, weight) =
+                    timestamp_bracket(&dt_local_array, unix_timestamps[index_1]);
+                let lower_row = indexed_forecast.slice(s![index_1, lower, ..]);
+                let upper_row = indexed_forecast.slice(s![index_1, upper, ..]);
+                for endpoint in 0..full_forecast_shape.2 {
+                    let w0 = lower_row[endpoint];
+                    let w1 = upper_row[endpoint];
+                    weather_in_time_arrayview[[index_1, endpoint]] = w0 + (w1 - w0) * weight;
+                }
+            }
+        } else {
+            // Nearest-timestamp snapping: select the single closest forecast row per tick.
+            let closest_timestamp_indices =
+                rust_closest_timestamp_indices(unix_timestamps, dt_local_array);
+            for (index_1, &index_2) in closest_timestamp_indices.iter().enumerate() {
+                let slice_1d = indexed_forecast
+                    .slice(s![index_1, index_2, ..])
+                    .into_shape(full_forecast_shape.2)
+                    .unwrap();
+                weather_in_time_arrayview
+                    .slice_mut(s![index_1, ..])
+                    .assign(&slice_1d);
+            }
         }
 
         weather_in_time_arrayview.into_owned()
@@ -183,6 +507,137 @@ fn rust_simulation(_py: Python, m: &PyModule) -> PyResult<()> {
         closest_time_stamp_indices
     }
 
+    /// A state vector that a fixed-step integrator can advance in place. `assign` overwrites the
+    /// state with another, and `scaled_add` accumulates a scaled derivative (`self += diff *
+    /// scale`); these are the state operations the classic Runge-Kutta driver uses.
+    trait Integrable {
+        fn assign(&mut self, other: &Self);
+        fn scaled_add(&mut self, diff: &Self, scale: f64);
+    }
+
+    /// The vehicle's integrated quantities: battery state-of-charge together with the cumulative
+    /// consumed and generated energy.
+    #[derive(Clone, Default)]
+    struct EnergyState {
+        state_of_charge: f64,
+        consumed_energy: f64,
+        generated_energy: f64,
+    }
+
+    impl Integrable for EnergyState {
+        fn assign(&mut self, other: &Self) {
+            self.state_of_charge = other.state_of_charge;
+            self.consumed_energy = other.consumed_energy;
+            self.generated_energy = other.generated_energy;
+        }
+
+        fn scaled_add(&mut self, diff: &Self, scale: f64) {
+            self.state_of_charge += diff.state_of_charge * scale;
+            self.consumed_energy += diff.consumed_energy * scale;
+            self.generated_energy += diff.generated_energy * scale;
+        }
+    }
+
+    /// Advance `state` by one classic fixed-step RK4 step of width `h` under the derivative
+    /// closure `f(t, y)`.
+    fn rk4_step<S, F>(state: &S, t: f64, h: f64, f: &F) -> S
+    where
+        S: Integrable + Clone + Default,
+        F: Fn(f64, &S) -> S,
+    {
+        let k1 = f(t, state);
+
+        let mut y2 = state.clone();
+        y2.scaled_add(&k1, h / 2.0);
+        let k2 = f(t + h / 2.0, &y2);
+
+        let mut y3 = state.clone();
+        y3.scaled_add(&k2, h / 2.0);
+        let k3 = f(t + h / 2.0, &y3);
+
+        let mut y4 = state.clone();
+        y4.scaled_add(&k3, h);
+        let k4 = f(t + h, &y4);
+
+        let mut next = S::default();
+        next.assign(state);
+        next.scaled_add(&k1, h / 6.0);
+        next.scaled_add(&k2, h / 3.0);
+        next.scaled_add(&k3, h / 3.0);
+        next.scaled_add(&k4, h / 6.0);
+        next
+    }
+
+    /// Linearly sample a per-tick array at a continuous tick position, clamping at the ends so
+    /// the RK4 half-steps stay within the forecast horizon.
+    fn sample_per_tick(values: &[f64], position: f64) -> f64 {
+        let last = values.len() - 1;
+        if position <= 0.0 {
+            return values[0];
+        }
+        if position >= last as f64 {
+            return values[last];
+        }
+        let lower = position.floor() as usize;
+        let fraction = position - lower as f64;
+        values[lower] + (values[lower + 1] - values[lower]) * fraction
+    }
+
+    /// Step the vehicle's state-of-charge and energy trajectories forward with RK4 from the
+    /// per-tick generation and consumption power arrays (watts). `pack_capacity` is the usable
+    /// pack energy in joules, used to convert net power into a SoC rate. SoC is clamped to
+    /// `[0, 1]` after each step; the returned flag is the first tick at which the pack is depleted
+    /// (`-1` if it never is).
+    fn rust_integrate_energy(
+        generation: ArrayViewD<'_, f64>,
+        consumption: ArrayViewD<'_, f64>,
+        tick: f64,
+        initial_soc: f64,
+        pack_capacity: f64,
+    ) -> (Vec<f64>, Vec<f64>, Vec<f64>, i64) {
+        let ticks = generation.len();
+        let generation = generation.as_slice().unwrap();
+        let consumption = consumption.as_slice().unwrap();
+
+        let mut soc_out: Vec<f64> = Vec::with_capacity(ticks);
+        let mut consumed_out: Vec<f64> = Vec::with_capacity(ticks);
+        let mut generated_out: Vec<f64> = Vec::with_capacity(ticks);
+
+        // Net power drives SoC (per second) and accumulates consumed/generated energy.
+        let derivative = |t: f64, _y: &EnergyState| {
+            let position = t / tick;
+            let generated_power = sample_per_tick(generation, position);
+            let consumed_power = sample_per_tick(consumption, position);
+            EnergyState {
+                state_of_charge: (generated_power - consumed_power) / pack_capacity,
+                consumed_energy: consumed_power,
+                generated_energy: generated_power,
+            }
+        };
+
+        let mut state = EnergyState {
+            state_of_charge: initial_soc.clamp(0.0, 1.0),
+            ..Default::default()
+        };
+        let mut depleted_tick: i64 = -1;
+
+        for tick_index in 0..ticks {
+            let t = tick_index as f64 * tick;
+            state = rk4_step(&state, t, tick, &derivative);
+            state.state_of_charge = state.state_of_charge.clamp(0.0, 1.0);
+
+            if depleted_tick < 0 && state.state_of_charge <= 0.0 {
+                depleted_tick = tick_index as i64;
+            }
+
+            soc_out.push(state.state_of_charge);
+            consumed_out.push(state.consumed_energy);
+            generated_out.push(state.generated_energy);
+        }
+
+        (soc_out, consumed_out, generated_out, depleted_tick)
+    }
+
     #[pyfn(m)]
     #[pyo3(name = "constrain_speeds")]
     fn constrain_speeds_py<'py>(py: Python<'py>, x: PyReadwriteArrayDyn<'py, f64>, y: PyReadwriteArrayDyn<'py, f64>, z: i32) -> &'py PyArrayDyn<f64> {
@@ -205,6 +660,71 @@ fn rust_simulation(_py: Python, m: &PyModule) -> PyResult<()> {
         (py_day_out, py_time_out)
     }
 
+    #[pyfn(m)]
+    #[pyo3(name = "solar_position")]
+    fn solar_position<'py>(
+        py: Python<'py>,
+        python_unix_timestamps: PyReadwriteArrayDyn<'py, i64>,
+        python_latitudes: PyReadwriteArrayDyn<'py, f64>,
+        python_longitudes: PyReadwriteArrayDyn<'py, f64>,
+        panel_tilt: f64,
+        panel_azimuth: f64,
+        zenith_limit: f64,
+    ) -> (
+        &'py PyArrayDyn<f64>,
+        &'py PyArrayDyn<f64>,
+        &'py PyArrayDyn<f64>,
+        &'py PyArrayDyn<f64>,
+        &'py PyArrayDyn<f64>,
+    ) {
+        let unix_timestamps = python_unix_timestamps.as_array();
+        let latitudes = python_latitudes.as_array();
+        let longitudes = python_longitudes.as_array();
+        let (zenith, azimuth, declination, hour_angle, incidence) = rust_solar_position(
+            unix_timestamps,
+            latitudes,
+            longitudes,
+            panel_tilt,
+            panel_azimuth,
+            zenith_limit,
+        );
+        (
+            PyArray::from_vec(py, zenith).to_dyn(),
+            PyArray::from_vec(py, azimuth).to_dyn(),
+            PyArray::from_vec(py, declination).to_dyn(),
+            PyArray::from_vec(py, hour_angle).to_dyn(),
+            PyArray::from_vec(py, incidence).to_dyn(),
+        )
+    }
+
+    #[pyfn(m)]
+    #[pyo3(name = "incident_irradiance")]
+    fn incident_irradiance<'py>(
+        py: Python<'py>,
+        python_unix_timestamps: PyReadwriteArrayDyn<'py, i64>,
+        python_latitudes: PyReadwriteArrayDyn<'py, f64>,
+        python_longitudes: PyReadwriteArrayDyn<'py, f64>,
+        python_ghi: PyReadwriteArrayDyn<'py, f64>,
+        panel_tilt: f64,
+        panel_azimuth: f64,
+        zenith_limit: f64,
+    ) -> &'py PyArrayDyn<f64> {
+        let unix_timestamps = python_unix_timestamps.as_array();
+        let latitudes = python_latitudes.as_array();
+        let longitudes = python_longitudes.as_array();
+        let ghi = python_ghi.as_array();
+        let result = rust_incident_irradiance(
+            unix_timestamps,
+            latitudes,
+            longitudes,
+            ghi,
+            panel_tilt,
+            panel_azimuth,
+            zenith_limit,
+        );
+        PyArray::from_vec(py, result).to_dyn()
+    }
+
     #[pyfn(m)]
     #[pyo3(name = "closest_gis_indices_loop")]
     fn closest_gis_indices_loop<'py>(
@@ -233,6 +753,67 @@ fn rust_simulation(_py: Python, m: &PyModule) -> PyResult<()> {
         py_result
     }
 
+    #[pyfn(m)]
+    #[pyo3(name = "closest_indices_haversine")]
+    fn closest_indices_haversine<'py>(
+        py: Python<'py>,
+        python_route_latitudes: PyReadwriteArrayDyn<'py, f64>,
+        python_route_longitudes: PyReadwriteArrayDyn<'py, f64>,
+        python_grid_latitudes: PyReadwriteArrayDyn<'py, f64>,
+        python_grid_longitudes: PyReadwriteArrayDyn<'py, f64>,
+        strict: bool,
+    ) -> &'py PyArrayDyn<i64> {
+        let route_latitudes = python_route_latitudes.as_array();
+        let route_longitudes = python_route_longitudes.as_array();
+        let grid_latitudes = python_grid_latitudes.as_array();
+        let grid_longitudes = python_grid_longitudes.as_array();
+        let result = rust_closest_indices_haversine(
+            route_latitudes,
+            route_longitudes,
+            grid_latitudes,
+            grid_longitudes,
+            strict,
+        );
+        let py_result = PyArray::from_vec(py, result).to_dyn();
+        py_result
+    }
+
+    #[pyfn(m)]
+    #[pyo3(name = "fractional_indices_loop")]
+    fn fractional_indices_loop<'py>(
+        py: Python<'py>,
+        python_cumulative_distances: PyReadwriteArrayDyn<'py, f64>,
+        python_average_distances: PyReadwriteArrayDyn<'py, f64>,
+    ) -> (&'py PyArrayDyn<i64>, &'py PyArrayDyn<f64>) {
+        let cumulative_distances = python_cumulative_distances.as_array();
+        let average_distances = python_average_distances.as_array();
+        let (lower_indices, alphas) =
+            rust_fractional_indices_loop(cumulative_distances, average_distances);
+        (
+            PyArray::from_vec(py, lower_indices).to_dyn(),
+            PyArray::from_vec(py, alphas).to_dyn(),
+        )
+    }
+
+    #[pyfn(m)]
+    #[pyo3(name = "blend_weather")]
+    fn blend_weather<'py>(
+        py: Python<'py>,
+        python_weather: PyReadwriteArrayDyn<'py, f64>,
+        python_lower_indices: PyReadwriteArrayDyn<'py, i64>,
+        python_alphas: PyReadwriteArrayDyn<'py, f64>,
+    ) -> &'py PyArrayDyn<f64> {
+        let weather = python_weather.as_array();
+        let lower_indices = python_lower_indices.as_array();
+        let alphas = python_alphas.as_array();
+        let mut result = rust_blend_weather(
+            weather,
+            lower_indices.as_slice().unwrap(),
+            alphas.as_slice().unwrap(),
+        );
+        PyArray::from_array(py, &mut result).to_dyn()
+    }
+
     #[pyfn(m)]
     #[pyo3(name = "weather_in_time")]
     fn weather_in_time<'py>(
@@ -240,15 +821,44 @@ fn rust_simulation(_py: Python, m: &PyModule) -> PyResult<()> {
         python_unix_timestamps: PyReadwriteArrayDyn<'py, i64>,
         python_indices: PyReadwriteArrayDyn<'py, i64>,
         python_weather_forecast: PyReadwriteArrayDyn<'py, f64>,
-        index: u8
+        index: u8,
+        mode: u8,
     ) -> &'py PyArrayDyn<f64> {
         let unix_timestamps = python_unix_timestamps.as_array();
         let indices = python_indices.as_array();
         let weather_forecast = python_weather_forecast.as_array();
-        let mut result = rust_weather_in_time(unix_timestamps, indices, weather_forecast, index);
+        let mut result =
+            rust_weather_in_time(unix_timestamps, indices, weather_forecast, index, mode);
         let py_result = PyArray::from_array(py, &mut result).to_dyn();
         py_result
     }
 
+    #[pyfn(m)]
+    #[pyo3(name = "integrate_energy")]
+    fn integrate_energy<'py>(
+        py: Python<'py>,
+        python_generation: PyReadwriteArrayDyn<'py, f64>,
+        python_consumption: PyReadwriteArrayDyn<'py, f64>,
+        tick: f64,
+        initial_soc: f64,
+        pack_capacity: f64,
+    ) -> (
+        &'py PyArrayDyn<f64>,
+        &'py PyArrayDyn<f64>,
+        &'py PyArrayDyn<f64>,
+        i64,
+    ) {
+        let generation = python_generation.as_array();
+        let consumption = python_consumption.as_array();
+        let (soc_out, consumed_out, generated_out, depleted_tick) =
+            rust_integrate_energy(generation, consumption, tick, initial_soc, pack_capacity);
+        (
+            PyArray::from_vec(py, soc_out).to_dyn(),
+            PyArray::from_vec(py, consumed_out).to_dyn(),
+            PyArray::from_vec(py, generated_out).to_dyn(),
+            depleted_tick,
+        )
+    }
+
     Ok(())
 }